@@ -0,0 +1,182 @@
+#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+
+# Inventory the benchmark crates, micro-benchmarks and reproduced bug cases
+# of this artifact, check that each of them builds with the toolchain pinned
+# in its study.toml, and write the result as a JSON manifest.
+#
+# Usage: ./manifest.py [--no-build] [OUT_FILE]
+#
+# OUT_FILE defaults to artifact-manifest.json. The build status of a crate is
+# one of: ok, failed, known-failure (fails as described in its study.toml),
+# missing-dependency (a path dependency that has to be checked out by hand,
+# see the crate's main.rs), toolchain-missing, environment-error (cargo could
+# not download the dependencies), or not-checked (with --no-build). The script
+# exits with 1 if a crate has no study.toml, fails to build unexpectedly, or
+# could not be checked because of an environment error.
+#
+# Every crate has a study.toml next to its Cargo.toml:
+#
+#     kind = "benchmark"          # or "bug"
+#     purpose = "What the crate shows in the paper"
+#     toolchain = "nightly"       # passed to cargo as +TOOLCHAIN
+#     known_failure = "Why it does not build"   # optional
+#
+# Only this flat `key = "string"` subset of TOML is read, so that the script
+# runs on Python 3.5 or newer without extra modules.
+
+import json
+import os
+import re
+import subprocess
+import sys
+
+ROOT_DIR = os.path.dirname(os.path.abspath(__file__))
+
+KINDS = ["benchmark", "bug"]
+
+BENCH_RE = re.compile(r"#\[bench\]\s*fn\s+(\w+)")
+SECTION_RE = re.compile(r"^\s*\[([^\]]*)\]\s*$")
+STRING_RE = re.compile(r"^\s*([\w-]+)\s*=\s*\"([^\"]*)\"\s*(#.*)?$")
+PATH_RE = re.compile(r"\bpath\s*=\s*\"([^\"]*)\"")
+NETWORK_ERROR_RE = re.compile(r"failed to fetch|failed to download|Could not resolve host|"
+                              r"spurious network error|failed to update registry")
+
+def find_crates():
+    crate_dirs = []
+    for root, dirs, files in os.walk(ROOT_DIR):
+        dirs[:] = sorted(d for d in dirs if d != "target" and not d.startswith("."))
+        if "Cargo.toml" in files and root != ROOT_DIR:
+            # Skip the vendored library sources inside a bug case.
+            if not any(root.startswith(d + os.sep) for d in crate_dirs):
+                crate_dirs.append(root)
+    # Skip library checkouts that a case uses as a path dependency (e.g.
+    # cve/crossbeam); they belong to that case.
+    deps = set()
+    for crate_dir in crate_dirs:
+        _, paths = read_cargo(os.path.join(crate_dir, "Cargo.toml"))
+        deps.update(os.path.realpath(os.path.join(crate_dir, p)) for p in paths)
+    return [d for d in crate_dirs if os.path.realpath(d) not in deps]
+
+def read_study(path):
+    """The top-level `key = "string"` pairs of a study.toml."""
+    study = {}
+    with open(path) as infile:
+        for line in infile:
+            m = STRING_RE.match(line)
+            if m is not None:
+                study[m.group(1)] = m.group(2)
+    return study
+
+def read_cargo(path):
+    """Package name and path dependencies of a Cargo.toml."""
+    name = None
+    paths = []
+    section = ""
+    with open(path) as infile:
+        for line in infile:
+            m = SECTION_RE.match(line)
+            if m is not None:
+                section = m.group(1).strip()
+                continue
+            if section == "package":
+                m = STRING_RE.match(line)
+                if m is not None and m.group(1) == "name":
+                    name = m.group(2)
+            elif section.split(".")[0] in ["dependencies", "dev-dependencies"]:
+                paths += PATH_RE.findall(line)
+    return name, paths
+
+def missing_path_deps(crate_dir, paths):
+    return [p for p in paths if not os.path.exists(os.path.join(crate_dir, p, "Cargo.toml"))]
+
+def benchmarks(crate_dir):
+    names = []
+    for root, _, files in os.walk(os.path.join(crate_dir, "src")):
+        for name in sorted(files):
+            if name.endswith(".rs"):
+                with open(os.path.join(root, name), errors="replace") as infile:
+                    names += BENCH_RE.findall(infile.read())
+    return names
+
+def installed_toolchains():
+    output = subprocess.run(["rustup", "toolchain", "list"], stdout=subprocess.PIPE)
+    return [line.split()[0] for line in output.stdout.decode("utf-8").splitlines() if line.strip() != ""]
+
+def toolchain_installed(toolchain, installed):
+    return any(t == toolchain or t.startswith(toolchain + "-") for t in installed)
+
+def rustc_version(toolchain):
+    output = subprocess.run(["rustc", "+" + toolchain, "-V"], stdout=subprocess.PIPE)
+    return output.stdout.decode("utf-8").strip()
+
+def build(crate_dir, toolchain):
+    result = subprocess.run(["cargo", "+" + toolchain, "build", "--all-targets", "-q"],
+                            cwd=crate_dir, stdout=subprocess.DEVNULL, stderr=subprocess.PIPE)
+    if result.returncode == 0:
+        return "ok"
+    if NETWORK_ERROR_RE.search(result.stderr.decode("utf-8", errors="replace")):
+        return "environment-error"
+    return "failed"
+
+def main():
+    args = sys.argv[1:]
+    check_build = "--no-build" not in args
+    args = [a for a in args if a != "--no-build"]
+    out_file = args[0] if args else os.path.join(ROOT_DIR, "artifact-manifest.json")
+
+    installed = installed_toolchains() if check_build else []
+    toolchains = {}
+    artifacts = []
+    error = False
+    for crate_dir in find_crates():
+        path = os.path.relpath(crate_dir, ROOT_DIR)
+        name, paths = read_cargo(os.path.join(crate_dir, "Cargo.toml"))
+        study_path = os.path.join(crate_dir, "study.toml")
+        if not os.path.exists(study_path):
+            print("missing study.toml:", path, file=sys.stderr)
+            error = True
+            study = {}
+        else:
+            study = read_study(study_path)
+            if study.get("kind") not in KINDS:
+                print("unknown kind in study.toml:", path, file=sys.stderr)
+                error = True
+        toolchain = study.get("toolchain", "stable")
+
+        artifact = {
+            "name": name or os.path.basename(crate_dir),
+            "path": path,
+            "section": path.split(os.sep)[0],
+            "kind": study.get("kind"),
+            "purpose": study.get("purpose"),
+            "toolchain": toolchain,
+            "benchmarks": benchmarks(crate_dir),
+        }
+        missing = missing_path_deps(crate_dir, paths)
+        if not check_build:
+            artifact["build"] = "not-checked"
+        elif missing:
+            artifact["build"] = "missing-dependency"
+            artifact["missing"] = missing
+        elif not toolchain_installed(toolchain, installed):
+            artifact["build"] = "toolchain-missing"
+        else:
+            if toolchain not in toolchains:
+                toolchains[toolchain] = rustc_version(toolchain)
+            artifact["build"] = build(crate_dir, toolchain)
+            if artifact["build"] == "failed" and "known_failure" in study:
+                artifact["build"] = "known-failure"
+                artifact["known_failure"] = study["known_failure"]
+            if artifact["build"] in ["failed", "environment-error"]:
+                error = True
+        print(artifact["path"] + ", " + artifact["build"])
+        artifacts.append(artifact)
+
+    with open(out_file, "w") as outfile:
+        json.dump({"toolchains": toolchains, "artifacts": artifacts}, outfile, indent=2)
+        outfile.write("\n")
+    sys.exit(1 if error else 0)
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,177 @@
+#![feature(test)]
+
+extern crate test;
+extern crate typed_arena;
+
+use std::ptr;
+use typed_arena::Arena;
+
+// Build a complete binary tree and traverse it, with nodes owned by
+// `Box`, by a typed arena, and by an unsafe pool linked through raw pointers.
+
+pub struct BoxNode {
+    pub val: u64,
+    pub left: Option<Box<BoxNode>>,
+    pub right: Option<Box<BoxNode>>,
+}
+
+pub fn build_box(depth: u32, val: u64) -> Box<BoxNode> {
+    let (left, right) = if depth == 0 {
+        (None, None)
+    } else {
+        (Some(build_box(depth - 1, 2 * val)), Some(build_box(depth - 1, 2 * val + 1)))
+    };
+    Box::new(BoxNode { val, left, right })
+}
+
+pub fn sum_box(node: &BoxNode) -> u64 {
+    let mut sum = node.val;
+    if let Some(ref left) = node.left {
+        sum += sum_box(left);
+    }
+    if let Some(ref right) = node.right {
+        sum += sum_box(right);
+    }
+    sum
+}
+
+pub struct ArenaNode<'a> {
+    pub val: u64,
+    pub left: Option<&'a ArenaNode<'a>>,
+    pub right: Option<&'a ArenaNode<'a>>,
+}
+
+pub fn build_arena<'a>(arena: &'a Arena<ArenaNode<'a>>, depth: u32, val: u64) -> &'a ArenaNode<'a> {
+    let (left, right) = if depth == 0 {
+        (None, None)
+    } else {
+        (Some(build_arena(arena, depth - 1, 2 * val)), Some(build_arena(arena, depth - 1, 2 * val + 1)))
+    };
+    arena.alloc(ArenaNode { val, left, right })
+}
+
+pub fn sum_arena(node: &ArenaNode) -> u64 {
+    let mut sum = node.val;
+    if let Some(left) = node.left {
+        sum += sum_arena(left);
+    }
+    if let Some(right) = node.right {
+        sum += sum_arena(right);
+    }
+    sum
+}
+
+pub struct RawNode {
+    pub val: u64,
+    pub left: *const RawNode,
+    pub right: *const RawNode,
+}
+
+/// All nodes live in one `Vec` that is sized up front and never grows,
+/// so the raw pointers between them stay valid as long as the pool lives.
+pub struct RawPool {
+    nodes: Vec<RawNode>,
+}
+
+impl RawPool {
+    pub fn with_depth(depth: u32) -> RawPool {
+        RawPool { nodes: Vec::with_capacity((1 << (depth + 1)) - 1) }
+    }
+
+    fn alloc(&mut self, node: RawNode) -> *const RawNode {
+        let idx = self.nodes.len();
+        assert!(idx < self.nodes.capacity());
+        self.nodes.push(node);
+        unsafe { self.nodes.as_ptr().add(idx) }
+    }
+}
+
+pub fn build_raw(pool: &mut RawPool, depth: u32, val: u64) -> *const RawNode {
+    let (left, right) = if depth == 0 {
+        (ptr::null(), ptr::null())
+    } else {
+        (build_raw(pool, depth - 1, 2 * val), build_raw(pool, depth - 1, 2 * val + 1))
+    };
+    pool.alloc(RawNode { val, left, right })
+}
+
+/// # Safety
+///
+/// `node` must come from `build_raw` on a pool that is still alive.
+pub unsafe fn sum_raw(node: *const RawNode) -> u64 {
+    let node = &*node;
+    let mut sum = node.val;
+    if !node.left.is_null() {
+        sum += sum_raw(node.left);
+    }
+    if !node.right.is_null() {
+        sum += sum_raw(node.right);
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::{black_box, Bencher};
+
+    const DEPTH: u32 = 16;
+
+    #[test]
+    fn same_sum() {
+        let boxed = build_box(DEPTH, 1);
+        let arena = Arena::new();
+        let arena_root = build_arena(&arena, DEPTH, 1);
+        let mut pool = RawPool::with_depth(DEPTH);
+        let raw_root = build_raw(&mut pool, DEPTH, 1);
+        let expected = sum_box(&boxed);
+        assert_eq!(expected, sum_arena(arena_root));
+        assert_eq!(expected, unsafe { sum_raw(raw_root) });
+    }
+
+    #[bench]
+    fn bench_build_box(b: &mut Bencher) {
+        b.iter(|| {
+            let root = build_box(black_box(DEPTH), 1);
+            black_box(&root);
+        });
+    }
+
+    #[bench]
+    fn bench_build_arena(b: &mut Bencher) {
+        b.iter(|| {
+            let arena = Arena::new();
+            let root = build_arena(&arena, black_box(DEPTH), 1);
+            black_box(root);
+        });
+    }
+
+    #[bench]
+    fn bench_build_raw(b: &mut Bencher) {
+        b.iter(|| {
+            let mut pool = RawPool::with_depth(DEPTH);
+            let root = build_raw(&mut pool, black_box(DEPTH), 1);
+            black_box(root);
+        });
+    }
+
+    #[bench]
+    fn bench_traverse_box(b: &mut Bencher) {
+        let root = build_box(DEPTH, 1);
+        b.iter(|| black_box(sum_box(&root)));
+    }
+
+    #[bench]
+    fn bench_traverse_arena(b: &mut Bencher) {
+        let arena = Arena::new();
+        let root = build_arena(&arena, DEPTH, 1);
+        b.iter(|| black_box(sum_arena(root)));
+    }
+
+    #[bench]
+    fn bench_traverse_raw(b: &mut Bencher) {
+        let mut pool = RawPool::with_depth(DEPTH);
+        let root = build_raw(&mut pool, DEPTH, 1);
+        b.iter(|| black_box(unsafe { sum_raw(root) }));
+    }
+}
@@ -0,0 +1,179 @@
+#![feature(test)]
+
+extern crate test;
+
+// One round of a block-mixing kernel, in the shape of the fixed-size
+// block loops of hash and cipher implementations.
+
+pub fn mix_array_32(state: &mut [u8; 32], block: &[u8; 32]) {
+    for i in 0..32 {
+        state[i] = (state[i] ^ block[31 - i]).rotate_left(3);
+        state[i] = state[i].wrapping_add(state[(i + 1) % 32]);
+    }
+}
+
+pub fn mix_array_64(state: &mut [u8; 64], block: &[u8; 64]) {
+    for i in 0..64 {
+        state[i] = (state[i] ^ block[63 - i]).rotate_left(3);
+        state[i] = state[i].wrapping_add(state[(i + 1) % 64]);
+    }
+}
+
+pub fn mix_const<const N: usize>(state: &mut [u8; N], block: &[u8; N]) {
+    for i in 0..N {
+        state[i] = (state[i] ^ block[N - 1 - i]).rotate_left(3);
+        state[i] = state[i].wrapping_add(state[(i + 1) % N]);
+    }
+}
+
+pub fn mix_slice(state: &mut [u8], block: &[u8]) {
+    assert_eq!(state.len(), block.len());
+    let n = state.len();
+    for i in 0..n {
+        state[i] = (state[i] ^ block[n - 1 - i]).rotate_left(3);
+        state[i] = state[i].wrapping_add(state[(i + 1) % n]);
+    }
+}
+
+/// # Safety
+///
+/// `block` must have the same length as `state`.
+pub unsafe fn mix_slice_unchecked(state: &mut [u8], block: &[u8]) {
+    debug_assert_eq!(state.len(), block.len());
+    let n = state.len();
+    for i in 0..n {
+        let s = (*state.get_unchecked(i) ^ *block.get_unchecked(n - 1 - i)).rotate_left(3);
+        *state.get_unchecked_mut(i) = s.wrapping_add(*state.get_unchecked((i + 1) % n));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::{black_box, Bencher};
+
+    const BLOCKS: usize = 4096;
+
+    fn input(block_size: usize) -> Vec<u8> {
+        (0..BLOCKS * block_size).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn same_result() {
+        let data = input(64);
+        let mut a = [0u8; 64];
+        let mut b = [0u8; 64];
+        let mut c = [0u8; 64];
+        let mut d = [0u8; 64];
+        let (blocks, _) = data.as_chunks::<64>();
+        for block in blocks {
+            mix_array_64(&mut a, block);
+            mix_const(&mut b, block);
+            mix_slice(&mut c, block);
+            unsafe { mix_slice_unchecked(&mut d, block) };
+        }
+        assert_eq!(a[..], b[..]);
+        assert_eq!(a[..], c[..]);
+        assert_eq!(a[..], d[..]);
+    }
+
+    #[bench]
+    fn bench_array_32(b: &mut Bencher) {
+        let data = input(32);
+        let mut state = [0u8; 32];
+        let (blocks, _) = data.as_chunks::<32>();
+        b.iter(|| {
+            for block in blocks {
+                mix_array_32(&mut state, block);
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_const_generic_32(b: &mut Bencher) {
+        let data = input(32);
+        let mut state = [0u8; 32];
+        let (blocks, _) = data.as_chunks::<32>();
+        b.iter(|| {
+            for block in blocks {
+                mix_const(&mut state, block);
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_slice_checked_32(b: &mut Bencher) {
+        let data = input(32);
+        let mut state = vec![0u8; black_box(32)];
+        b.iter(|| {
+            for chunk in data.chunks_exact(state.len()) {
+                mix_slice(&mut state, chunk);
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_slice_unchecked_32(b: &mut Bencher) {
+        let data = input(32);
+        let mut state = vec![0u8; black_box(32)];
+        b.iter(|| {
+            for chunk in data.chunks_exact(state.len()) {
+                unsafe { mix_slice_unchecked(&mut state, chunk) };
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_array_64(b: &mut Bencher) {
+        let data = input(64);
+        let mut state = [0u8; 64];
+        let (blocks, _) = data.as_chunks::<64>();
+        b.iter(|| {
+            for block in blocks {
+                mix_array_64(&mut state, block);
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_const_generic_64(b: &mut Bencher) {
+        let data = input(64);
+        let mut state = [0u8; 64];
+        let (blocks, _) = data.as_chunks::<64>();
+        b.iter(|| {
+            for block in blocks {
+                mix_const(&mut state, block);
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_slice_checked_64(b: &mut Bencher) {
+        let data = input(64);
+        let mut state = vec![0u8; black_box(64)];
+        b.iter(|| {
+            for chunk in data.chunks_exact(state.len()) {
+                mix_slice(&mut state, chunk);
+            }
+            black_box(&state);
+        });
+    }
+
+    #[bench]
+    fn bench_slice_unchecked_64(b: &mut Bencher) {
+        let data = input(64);
+        let mut state = vec![0u8; black_box(64)];
+        b.iter(|| {
+            for chunk in data.chunks_exact(state.len()) {
+                unsafe { mix_slice_unchecked(&mut state, chunk) };
+            }
+            black_box(&state);
+        });
+    }
+}
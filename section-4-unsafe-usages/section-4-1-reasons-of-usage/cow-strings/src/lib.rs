@@ -0,0 +1,144 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::borrow::Cow;
+
+// A tokenizing pipeline that lower-cases only the tokens that contain ASCII
+// upper-case letters, as parsers do when normalizing identifiers or header
+// names. Most tokens pass through unchanged.
+
+pub fn input(words: usize) -> String {
+    let mut s = String::new();
+    for i in 0..words {
+        if i % 4 == 0 {
+            s.push_str("Header-Name");
+        } else {
+            s.push_str("token");
+        }
+        s.push_str(&i.to_string());
+        s.push(' ');
+    }
+    s
+}
+
+fn needs_lower(token: &str) -> bool {
+    token.bytes().any(|b| b.is_ascii_uppercase())
+}
+
+fn consume(acc: u64, token: &str) -> u64 {
+    acc.wrapping_mul(31).wrapping_add(token.len() as u64 + token.as_bytes()[0] as u64)
+}
+
+pub fn normalize_cow(token: &str) -> Cow<'_, str> {
+    if needs_lower(token) {
+        Cow::Owned(token.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+pub fn pipeline_cow(text: &str) -> u64 {
+    text.split_ascii_whitespace()
+        .map(normalize_cow)
+        .fold(0, |acc, token| consume(acc, &token))
+}
+
+pub fn normalize_clone(token: &str) -> String {
+    let mut owned = token.to_string();
+    if needs_lower(&owned) {
+        owned.make_ascii_lowercase();
+    }
+    owned
+}
+
+pub fn pipeline_clone(text: &str) -> u64 {
+    text.split_ascii_whitespace()
+        .map(normalize_clone)
+        .fold(0, |acc, token| consume(acc, &token))
+}
+
+/// Tokens are spans into one shared buffer, split on the same ASCII
+/// whitespace as `split_ascii_whitespace`, and lower-cased in place
+/// through `as_bytes_mut`. Only ASCII bytes are changed, so the buffer stays
+/// valid UTF-8.
+pub fn pipeline_in_place(text: &mut str) -> u64 {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, b) in text.bytes().enumerate() {
+        match (b.is_ascii_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+
+    let bytes = unsafe { text.as_bytes_mut() };
+    let mut acc = 0;
+    for &(s, e) in &spans {
+        let token = &mut bytes[s..e];
+        if token.iter().any(|b| b.is_ascii_uppercase()) {
+            token.make_ascii_lowercase();
+        }
+        let token = unsafe { std::str::from_utf8_unchecked(token) };
+        acc = consume(acc, token);
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::{black_box, Bencher};
+
+    const WORDS: usize = 10000;
+
+    #[test]
+    fn same_result() {
+        let text = input(WORDS);
+        let expected = pipeline_cow(&text);
+        assert_eq!(expected, pipeline_clone(&text));
+        let mut buf = text.clone();
+        assert_eq!(expected, pipeline_in_place(&mut buf));
+        assert_eq!(buf, text.to_ascii_lowercase());
+    }
+
+    #[test]
+    fn same_tokens() {
+        let text = " Header-Name0\ttoken1\n\r\nHeader-Name2  token3\x0c";
+        let expected = pipeline_cow(text);
+        assert_eq!(expected, pipeline_clone(text));
+        let mut buf = text.to_string();
+        assert_eq!(expected, pipeline_in_place(&mut buf));
+        assert_eq!(buf, text.to_ascii_lowercase());
+    }
+
+    #[bench]
+    fn bench_cow(b: &mut Bencher) {
+        let text = input(WORDS);
+        b.iter(|| black_box(pipeline_cow(&text)));
+    }
+
+    #[bench]
+    fn bench_clone(b: &mut Bencher) {
+        let text = input(WORDS);
+        b.iter(|| black_box(pipeline_clone(&text)));
+    }
+
+    #[bench]
+    fn bench_in_place(b: &mut Bencher) {
+        let text = input(WORDS);
+        // Every iteration needs upper-case input again, so the copy of the
+        // buffer is part of the measured work.
+        b.iter(|| {
+            let mut buf = text.clone();
+            black_box(pipeline_in_place(&mut buf))
+        });
+    }
+}
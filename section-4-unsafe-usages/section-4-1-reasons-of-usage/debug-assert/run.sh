@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+#
+# Benchmark the loops at opt-level 0, 2 and 3, each with debug assertions
+# off and on, and print how much faster the debug_assert!/unchecked
+# versions are than the assert! version.
+
+for opt_level in 0 2 3; do
+    for debug_assertions in false true; do
+        echo "opt-level=${opt_level} debug-assertions=${debug_assertions}"
+        CARGO_PROFILE_BENCH_OPT_LEVEL=${opt_level} \
+        CARGO_PROFILE_BENCH_DEBUG_ASSERTIONS=${debug_assertions} \
+        cargo bench 2>/dev/null | grep 'bench:' | tr -d ',' | awk '
+            {
+                split($2, name, "_")
+                kernel = name[2]
+                variant = substr($2, length("tests::bench_" kernel "_") + 1)
+                ns[kernel, variant] = $5
+            }
+            END {
+                split("gather smooth", kernels, " ")
+                for (i = 1; i <= 2; i++) {
+                    k = kernels[i]
+                    base = ns[k, "assert"]
+                    printf "  %-7s assert: %12.2f ns/iter, debug_assert speedup: %5.2fx, unchecked speedup: %5.2fx\n",
+                           k, base, base / ns[k, "debug_assert"], base / ns[k, "unchecked"]
+                }
+            }'
+    done
+done
@@ -0,0 +1,160 @@
+#![feature(test)]
+
+extern crate test;
+
+// The same loops with three kinds of checks: `assert!` plus safe indexing,
+// `debug_assert!` plus `get_unchecked` (checked only when debug assertions
+// are on), and `get_unchecked` alone. `run.sh` builds them at several
+// optimization levels.
+
+pub fn gather_assert(data: &[u64], idx: &[usize]) -> u64 {
+    let mut sum = 0u64;
+    for &i in idx {
+        assert!(i < data.len());
+        sum = sum.wrapping_add(data[i]);
+    }
+    sum
+}
+
+/// # Safety
+///
+/// Every index in `idx` must be in bounds of `data`.
+pub unsafe fn gather_debug_assert(data: &[u64], idx: &[usize]) -> u64 {
+    let mut sum = 0u64;
+    for &i in idx {
+        debug_assert!(i < data.len());
+        sum = sum.wrapping_add(*data.get_unchecked(i));
+    }
+    sum
+}
+
+/// # Safety
+///
+/// Every index in `idx` must be in bounds of `data`.
+pub unsafe fn gather_unchecked(data: &[u64], idx: &[usize]) -> u64 {
+    let mut sum = 0u64;
+    for &i in idx {
+        sum = sum.wrapping_add(*data.get_unchecked(i));
+    }
+    sum
+}
+
+pub fn smooth_assert(src: &[u64], dst: &mut [u64]) {
+    assert!(src.len() >= 2 && src.len() == dst.len());
+    for i in 1..src.len() - 1 {
+        dst[i] = src[i - 1].wrapping_add(src[i]).wrapping_add(src[i + 1]) / 3;
+    }
+}
+
+/// # Safety
+///
+/// `src` must hold at least two elements, and `dst` must have the same
+/// length as `src`.
+pub unsafe fn smooth_debug_assert(src: &[u64], dst: &mut [u64]) {
+    debug_assert!(src.len() >= 2 && src.len() == dst.len());
+    for i in 1..src.len() - 1 {
+        *dst.get_unchecked_mut(i) = src
+            .get_unchecked(i - 1)
+            .wrapping_add(*src.get_unchecked(i))
+            .wrapping_add(*src.get_unchecked(i + 1))
+            / 3;
+    }
+}
+
+/// # Safety
+///
+/// `src` must hold at least two elements, and `dst` must have the same
+/// length as `src`.
+pub unsafe fn smooth_unchecked(src: &[u64], dst: &mut [u64]) {
+    for i in 1..src.len() - 1 {
+        *dst.get_unchecked_mut(i) = src
+            .get_unchecked(i - 1)
+            .wrapping_add(*src.get_unchecked(i))
+            .wrapping_add(*src.get_unchecked(i + 1))
+            / 3;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::{black_box, Bencher};
+
+    const LEN: usize = 100000;
+
+    fn data() -> Vec<u64> {
+        (0..LEN as u64).collect()
+    }
+
+    fn idx() -> Vec<usize> {
+        (0..LEN).map(|i| (i * 7919) % LEN).collect()
+    }
+
+    #[test]
+    fn same_result() {
+        let data = data();
+        let idx = idx();
+        let expected = gather_assert(&data, &idx);
+        assert_eq!(expected, unsafe { gather_debug_assert(&data, &idx) });
+        assert_eq!(expected, unsafe { gather_unchecked(&data, &idx) });
+
+        let mut a = vec![0; LEN];
+        let mut b = vec![0; LEN];
+        let mut c = vec![0; LEN];
+        smooth_assert(&data, &mut a);
+        unsafe {
+            smooth_debug_assert(&data, &mut b);
+            smooth_unchecked(&data, &mut c);
+        }
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+    }
+
+    #[bench]
+    fn bench_gather_assert(b: &mut Bencher) {
+        let (data, idx) = (data(), idx());
+        b.iter(|| black_box(gather_assert(&data, &idx)));
+    }
+
+    #[bench]
+    fn bench_gather_debug_assert(b: &mut Bencher) {
+        let (data, idx) = (data(), idx());
+        b.iter(|| black_box(unsafe { gather_debug_assert(&data, &idx) }));
+    }
+
+    #[bench]
+    fn bench_gather_unchecked(b: &mut Bencher) {
+        let (data, idx) = (data(), idx());
+        b.iter(|| black_box(unsafe { gather_unchecked(&data, &idx) }));
+    }
+
+    #[bench]
+    fn bench_smooth_assert(b: &mut Bencher) {
+        let src = data();
+        let mut dst = vec![0; LEN];
+        b.iter(|| {
+            smooth_assert(&src, &mut dst);
+            black_box(&dst);
+        });
+    }
+
+    #[bench]
+    fn bench_smooth_debug_assert(b: &mut Bencher) {
+        let src = data();
+        let mut dst = vec![0; LEN];
+        b.iter(|| {
+            unsafe { smooth_debug_assert(&src, &mut dst) };
+            black_box(&dst);
+        });
+    }
+
+    #[bench]
+    fn bench_smooth_unchecked(b: &mut Bencher) {
+        let src = data();
+        let mut dst = vec![0; LEN];
+        b.iter(|| {
+            unsafe { smooth_unchecked(&src, &mut dst) };
+            black_box(&dst);
+        });
+    }
+}
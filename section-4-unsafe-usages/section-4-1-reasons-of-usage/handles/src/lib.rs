@@ -0,0 +1,182 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Entities hold references to other entities and update themselves from
+// them every step. The references are raw pointers, generational indices
+// into a `Vec`, or `Rc<RefCell<..>>`.
+
+#[derive(Clone, Copy, Default)]
+pub struct Entity {
+    pub pos: u64,
+    pub vel: u64,
+}
+
+/// `targets` point into the buffer of `entities` and have the same length,
+/// so neither field may be touched from outside after `new`.
+pub struct RawWorld {
+    entities: Vec<Entity>,
+    targets: Vec<*const Entity>,
+}
+
+impl RawWorld {
+    pub fn new(n: usize) -> RawWorld {
+        let entities: Vec<Entity> = (0..n as u64).map(|i| Entity { pos: i, vel: 1 }).collect();
+        let base = entities.as_ptr();
+        let targets = (0..n).map(|i| unsafe { base.add(target_of(i, n)) }).collect();
+        RawWorld { entities, targets }
+    }
+
+    pub fn step(&mut self) {
+        let base = self.entities.as_mut_ptr();
+        for i in 0..self.targets.len() {
+            unsafe {
+                let target = (*self.targets[i]).pos;
+                let e = &mut *base.add(i);
+                e.pos = e.pos.wrapping_add(e.vel ^ target);
+            }
+        }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.entities.iter().fold(0, |acc, e| acc.wrapping_add(e.pos))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Handle {
+    pub index: usize,
+    pub generation: u32,
+}
+
+pub struct Slot {
+    pub generation: u32,
+    pub entity: Entity,
+}
+
+pub struct IndexWorld {
+    pub slots: Vec<Slot>,
+    pub targets: Vec<Handle>,
+}
+
+impl IndexWorld {
+    pub fn new(n: usize) -> IndexWorld {
+        let slots = (0..n as u64).map(|i| Slot { generation: 0, entity: Entity { pos: i, vel: 1 } }).collect();
+        let targets = (0..n).map(|i| Handle { index: target_of(i, n), generation: 0 }).collect();
+        IndexWorld { slots, targets }
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&Entity> {
+        match self.slots.get(handle.index) {
+            Some(slot) if slot.generation == handle.generation => Some(&slot.entity),
+            _ => None,
+        }
+    }
+
+    pub fn step(&mut self) {
+        for i in 0..self.targets.len() {
+            let target = match self.get(self.targets[i]) {
+                Some(e) => e.pos,
+                None => continue,
+            };
+            let e = &mut self.slots[i].entity;
+            e.pos = e.pos.wrapping_add(e.vel ^ target);
+        }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.slots.iter().fold(0, |acc, s| acc.wrapping_add(s.entity.pos))
+    }
+}
+
+pub struct RcWorld {
+    pub entities: Vec<Rc<RefCell<Entity>>>,
+    pub targets: Vec<Rc<RefCell<Entity>>>,
+}
+
+impl RcWorld {
+    pub fn new(n: usize) -> RcWorld {
+        let entities: Vec<_> = (0..n as u64)
+            .map(|i| Rc::new(RefCell::new(Entity { pos: i, vel: 1 })))
+            .collect();
+        let targets = (0..n).map(|i| entities[target_of(i, n)].clone()).collect();
+        RcWorld { entities, targets }
+    }
+
+    pub fn step(&mut self) {
+        for (e, t) in self.entities.iter().zip(self.targets.iter()) {
+            let target = t.borrow().pos;
+            let mut e = e.borrow_mut();
+            e.pos = e.pos.wrapping_add(e.vel ^ target);
+        }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.entities.iter().fold(0, |acc, e| acc.wrapping_add(e.borrow().pos))
+    }
+}
+
+/// Scatter the references so that they do not simply follow memory order.
+fn target_of(i: usize, n: usize) -> usize {
+    (i * 7919 + 13) % n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::{black_box, Bencher};
+
+    const ENTITY_NUM: usize = 10000;
+
+    #[test]
+    fn same_checksum() {
+        let mut raw = RawWorld::new(ENTITY_NUM);
+        let mut index = IndexWorld::new(ENTITY_NUM);
+        let mut rc = RcWorld::new(ENTITY_NUM);
+        for _ in 0..10 {
+            raw.step();
+            index.step();
+            rc.step();
+        }
+        assert_eq!(raw.checksum(), index.checksum());
+        assert_eq!(raw.checksum(), rc.checksum());
+    }
+
+    #[test]
+    fn stale_handle() {
+        let mut index = IndexWorld::new(4);
+        let handle = Handle { index: 1, generation: 0 };
+        index.slots[1].generation += 1;
+        assert!(index.get(handle).is_none());
+    }
+
+    #[bench]
+    fn bench_raw_pointer(b: &mut Bencher) {
+        let mut world = RawWorld::new(ENTITY_NUM);
+        b.iter(|| {
+            world.step();
+            black_box(&world.entities);
+        });
+    }
+
+    #[bench]
+    fn bench_generational_index(b: &mut Bencher) {
+        let mut world = IndexWorld::new(ENTITY_NUM);
+        b.iter(|| {
+            world.step();
+            black_box(&world.slots);
+        });
+    }
+
+    #[bench]
+    fn bench_rc_refcell(b: &mut Bencher) {
+        let mut world = RcWorld::new(ENTITY_NUM);
+        b.iter(|| {
+            world.step();
+            black_box(&world.entities);
+        });
+    }
+}
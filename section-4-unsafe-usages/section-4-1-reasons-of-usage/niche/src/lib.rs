@@ -0,0 +1,231 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::ptr::{self, NonNull};
+
+// Walk a linked list whose links are `Option<NonNull<T>>` (the compiler
+// uses the null niche, so the link is one pointer wide), `*mut T` with null
+// as the end marker, or a hand-written tag + union.
+
+pub struct OptionNode {
+    pub val: u64,
+    pub next: Option<NonNull<OptionNode>>,
+}
+
+pub struct RawNode {
+    pub val: u64,
+    pub next: *mut RawNode,
+}
+
+#[derive(Clone, Copy)]
+pub union LinkPayload {
+    pub ptr: *mut TaggedNode,
+    pub none: (),
+}
+
+#[derive(Clone, Copy)]
+pub struct TaggedLink {
+    pub tag: u8,
+    pub payload: LinkPayload,
+}
+
+pub const TAG_NONE: u8 = 0;
+pub const TAG_SOME: u8 = 1;
+
+pub struct TaggedNode {
+    pub val: u64,
+    pub next: TaggedLink,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Visiting order of the nodes, scattered over the pool. The stride is the
+/// first number from 7919 on that is coprime to `n`, so this is always a
+/// permutation.
+fn order(n: usize) -> Vec<usize> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let stride = (7919..).find(|&s| gcd(s, n) == 1).unwrap();
+    (0..n).map(|i| (i * stride) % n).collect()
+}
+
+pub fn build_option(n: usize) -> (Vec<OptionNode>, Option<NonNull<OptionNode>>) {
+    let mut pool: Vec<OptionNode> = (0..n as u64)
+        .map(|val| OptionNode { val, next: None })
+        .collect();
+    let base = pool.as_mut_ptr();
+    let order = order(n);
+    for w in order.windows(2) {
+        unsafe { (*base.add(w[0])).next = NonNull::new(base.add(w[1])) };
+    }
+    let head = order
+        .first()
+        .and_then(|&i| NonNull::new(unsafe { base.add(i) }));
+    (pool, head)
+}
+
+/// # Safety
+///
+/// `link` must be `None` or the head of a list built by `build_option`
+/// whose pool is still alive.
+pub unsafe fn sum_option(mut link: Option<NonNull<OptionNode>>) -> u64 {
+    let mut sum = 0u64;
+    while let Some(node) = link {
+        let node = node.as_ref();
+        sum = sum.wrapping_add(node.val);
+        link = node.next;
+    }
+    sum
+}
+
+pub fn build_raw(n: usize) -> (Vec<RawNode>, *mut RawNode) {
+    let mut pool: Vec<RawNode> = (0..n as u64)
+        .map(|val| RawNode {
+            val,
+            next: ptr::null_mut(),
+        })
+        .collect();
+    let base = pool.as_mut_ptr();
+    let order = order(n);
+    for w in order.windows(2) {
+        unsafe { (*base.add(w[0])).next = base.add(w[1]) };
+    }
+    let head = match order.first() {
+        Some(&i) => unsafe { base.add(i) },
+        None => ptr::null_mut(),
+    };
+    (pool, head)
+}
+
+/// # Safety
+///
+/// `link` must be null or the head of a list built by `build_raw` whose
+/// pool is still alive.
+pub unsafe fn sum_raw(mut link: *mut RawNode) -> u64 {
+    let mut sum = 0u64;
+    while !link.is_null() {
+        sum = sum.wrapping_add((*link).val);
+        link = (*link).next;
+    }
+    sum
+}
+
+pub fn build_tagged(n: usize) -> (Vec<TaggedNode>, TaggedLink) {
+    let none = TaggedLink {
+        tag: TAG_NONE,
+        payload: LinkPayload { none: () },
+    };
+    let mut pool: Vec<TaggedNode> = (0..n as u64)
+        .map(|val| TaggedNode { val, next: none })
+        .collect();
+    let base = pool.as_mut_ptr();
+    let order = order(n);
+    for w in order.windows(2) {
+        unsafe {
+            (*base.add(w[0])).next = TaggedLink {
+                tag: TAG_SOME,
+                payload: LinkPayload {
+                    ptr: base.add(w[1]),
+                },
+            };
+        }
+    }
+    let head = match order.first() {
+        Some(&i) => TaggedLink {
+            tag: TAG_SOME,
+            payload: LinkPayload {
+                ptr: unsafe { base.add(i) },
+            },
+        },
+        None => none,
+    };
+    (pool, head)
+}
+
+/// # Safety
+///
+/// `link` must come from `build_tagged` whose pool is still alive.
+pub unsafe fn sum_tagged(mut link: TaggedLink) -> u64 {
+    let mut sum = 0u64;
+    while link.tag == TAG_SOME {
+        let node = &*link.payload.ptr;
+        sum = sum.wrapping_add(node.val);
+        link = node.next;
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+    use test::{black_box, Bencher};
+
+    const NODES: usize = 100000;
+
+    #[test]
+    fn layout() {
+        assert_eq!(
+            size_of::<Option<NonNull<OptionNode>>>(),
+            size_of::<*mut RawNode>()
+        );
+        assert!(size_of::<TaggedLink>() > size_of::<*mut TaggedNode>());
+    }
+
+    #[test]
+    fn same_sum() {
+        let (_option_pool, option_head) = build_option(NODES);
+        let (_raw_pool, raw_head) = build_raw(NODES);
+        let (_tagged_pool, tagged_head) = build_tagged(NODES);
+        let expected = (0..NODES as u64).sum::<u64>();
+        assert_eq!(expected, unsafe { sum_option(option_head) });
+        assert_eq!(expected, unsafe { sum_raw(raw_head) });
+        assert_eq!(expected, unsafe { sum_tagged(tagged_head) });
+    }
+
+    #[test]
+    fn empty_list() {
+        let (_option_pool, option_head) = build_option(0);
+        let (_raw_pool, raw_head) = build_raw(0);
+        let (_tagged_pool, tagged_head) = build_tagged(0);
+        assert_eq!(0, unsafe { sum_option(option_head) });
+        assert_eq!(0, unsafe { sum_raw(raw_head) });
+        assert_eq!(0, unsafe { sum_tagged(tagged_head) });
+    }
+
+    #[test]
+    fn stride_multiple() {
+        let n = 7919 * 2;
+        let mut visited = order(n);
+        visited.sort_unstable();
+        assert!(visited.iter().copied().eq(0..n));
+        let (_raw_pool, raw_head) = build_raw(n);
+        assert_eq!((0..n as u64).sum::<u64>(), unsafe { sum_raw(raw_head) });
+    }
+
+    #[bench]
+    fn bench_option_nonnull(b: &mut Bencher) {
+        let (_pool, head) = build_option(NODES);
+        b.iter(|| black_box(unsafe { sum_option(black_box(head)) }));
+    }
+
+    #[bench]
+    fn bench_raw_null(b: &mut Bencher) {
+        let (_pool, head) = build_raw(NODES);
+        b.iter(|| black_box(unsafe { sum_raw(black_box(head)) }));
+    }
+
+    #[bench]
+    fn bench_tagged_union(b: &mut Bencher) {
+        let (_pool, head) = build_tagged(NODES);
+        b.iter(|| black_box(unsafe { sum_tagged(black_box(head)) }));
+    }
+}
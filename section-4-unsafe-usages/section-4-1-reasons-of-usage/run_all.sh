@@ -8,3 +8,24 @@ cargo bench 2>/dev/null
 
 cd ../array-offset
 cargo bench 2>/dev/null
+
+cd ../const-size
+cargo bench 2>/dev/null
+
+cd ../arena
+cargo bench 2>/dev/null
+
+cd ../handles
+cargo bench 2>/dev/null
+
+cd ../cow-strings
+cargo bench 2>/dev/null
+
+cd ../debug-assert
+./run.sh
+
+cd ../stencil
+cargo bench 2>/dev/null
+
+cd ../niche
+cargo bench 2>/dev/null
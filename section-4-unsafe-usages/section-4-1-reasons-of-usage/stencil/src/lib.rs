@@ -0,0 +1,148 @@
+#![feature(test)]
+
+extern crate test;
+
+// One 5-point Jacobi step over an n x n grid (the border is left as is),
+// with the grid stored as nested `Vec`s, as a flat buffer accessed through
+// `get_unchecked`, and as a flat buffer traversed with iterators.
+
+pub fn step_nested(src: &[Vec<f64>], dst: &mut [Vec<f64>]) {
+    let n = src.len();
+    for i in 1..n - 1 {
+        for j in 1..n - 1 {
+            dst[i][j] = 0.2 * (src[i][j] + src[i - 1][j] + src[i + 1][j] + src[i][j - 1] + src[i][j + 1]);
+        }
+    }
+}
+
+/// # Safety
+///
+/// `n` must not be 0, and `src` and `dst` must both hold at least `n * n`
+/// elements.
+pub unsafe fn step_flat_unchecked(src: &[f64], dst: &mut [f64], n: usize) {
+    for i in 1..n - 1 {
+        for j in 1..n - 1 {
+            let k = i * n + j;
+            *dst.get_unchecked_mut(k) = 0.2
+                * (*src.get_unchecked(k)
+                    + *src.get_unchecked(k - n)
+                    + *src.get_unchecked(k + n)
+                    + *src.get_unchecked(k - 1)
+                    + *src.get_unchecked(k + 1));
+        }
+    }
+}
+
+pub fn step_flat_iter(src: &[f64], dst: &mut [f64], n: usize) {
+    let rows = src.chunks_exact(n);
+    let above = rows.clone();
+    let below = rows.clone().skip(2);
+    let middle = rows.skip(1);
+    let out = dst.chunks_exact_mut(n).skip(1);
+    for (((up, row), down), out) in above.zip(middle).zip(below).zip(out) {
+        for (((w, u), d), o) in row.windows(3).zip(&up[1..]).zip(&down[1..]).zip(&mut out[1..n - 1]) {
+            *o = 0.2 * (w[1] + u + d + w[0] + w[2]);
+        }
+    }
+}
+
+pub fn grid(n: usize) -> Vec<f64> {
+    (0..n * n).map(|k| (k % 17) as f64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::{black_box, Bencher};
+
+    fn nested(n: usize) -> Vec<Vec<f64>> {
+        grid(n).chunks(n).map(|row| row.to_vec()).collect()
+    }
+
+    #[test]
+    fn same_result() {
+        let n = 37;
+        let src = grid(n);
+        let mut a = nested(n);
+        let mut b = src.clone();
+        let mut c = src.clone();
+        step_nested(&nested(n), &mut a);
+        unsafe { step_flat_unchecked(&src, &mut b, n) };
+        step_flat_iter(&src, &mut c, n);
+        let a: Vec<f64> = a.into_iter().flatten().collect();
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+    }
+
+    fn bench_nested(b: &mut Bencher, n: usize) {
+        let src = nested(n);
+        let mut dst = nested(n);
+        b.iter(|| {
+            step_nested(&src, &mut dst);
+            black_box(&dst);
+        });
+    }
+
+    fn bench_flat_unchecked(b: &mut Bencher, n: usize) {
+        let src = grid(n);
+        let mut dst = grid(n);
+        b.iter(|| {
+            unsafe { step_flat_unchecked(&src, &mut dst, n) };
+            black_box(&dst);
+        });
+    }
+
+    fn bench_flat_iter(b: &mut Bencher, n: usize) {
+        let src = grid(n);
+        let mut dst = grid(n);
+        b.iter(|| {
+            step_flat_iter(&src, &mut dst, n);
+            black_box(&dst);
+        });
+    }
+
+    #[bench]
+    fn bench_nested_64(b: &mut Bencher) {
+        bench_nested(b, 64);
+    }
+
+    #[bench]
+    fn bench_flat_unchecked_64(b: &mut Bencher) {
+        bench_flat_unchecked(b, 64);
+    }
+
+    #[bench]
+    fn bench_flat_iter_64(b: &mut Bencher) {
+        bench_flat_iter(b, 64);
+    }
+
+    #[bench]
+    fn bench_nested_256(b: &mut Bencher) {
+        bench_nested(b, 256);
+    }
+
+    #[bench]
+    fn bench_flat_unchecked_256(b: &mut Bencher) {
+        bench_flat_unchecked(b, 256);
+    }
+
+    #[bench]
+    fn bench_flat_iter_256(b: &mut Bencher) {
+        bench_flat_iter(b, 256);
+    }
+
+    #[bench]
+    fn bench_nested_1024(b: &mut Bencher) {
+        bench_nested(b, 1024);
+    }
+
+    #[bench]
+    fn bench_flat_unchecked_1024(b: &mut Bencher) {
+        bench_flat_unchecked(b, 1024);
+    }
+
+    #[bench]
+    fn bench_flat_iter_1024(b: &mut Bencher) {
+        bench_flat_iter(b, 1024);
+    }
+}
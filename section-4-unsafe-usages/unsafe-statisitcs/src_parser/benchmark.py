@@ -0,0 +1,127 @@
+# -*- coding: utf-8 -*-
+
+# Shared helpers for the Python analyses over the benchmark snapshots.
+
+import os
+import re
+import subprocess
+import sys
+
+SRC_PARSER_DIR = os.path.dirname(os.path.abspath(__file__))
+
+sys.path.append(os.path.join(SRC_PARSER_DIR, "unsafe_block_extractor"))
+sys.path.append(os.path.join(SRC_PARSER_DIR, "feature_gate_extractor"))
+sys.path.append(os.path.join(SRC_PARSER_DIR, "safe_wrapper_extractor"))
+
+from unsafe_block_extractor import extract_macro
+
+COMMENT_REMOVER = os.path.join(SRC_PARSER_DIR, "comment_remover", "comment_remover")
+
+# Same benchmark layout as run_all.sh: (name, directories under COUNT_DIR)
+PROJECTS = [
+    ("rand", ["rand"]),
+    ("crossbeam", ["crossbeam"]),
+    ("threadpool", ["rust-threadpool"]),
+    ("rayon", ["rayon"]),
+    ("lazy-static.rs", ["lazy-static.rs"]),
+    ("servo", ["servo"]),
+    ("tikv", ["tikv"]),
+    ("ethereum", ["parity-ethereum"]),
+    ("redox", ["redox"]),
+    ("tock", ["tock"]),
+    ("std", ["rust/src/libstd", "rust/src/libcore", "rust/src/liballoc"]),
+]
+
+# Adjustments run_all.sh makes on top of the plain counts:
+# unsafe fn false positives subtracted per directory (parse_libcore and
+# parse_liballoc), and precomputed "fn region trait" counts added to a
+# project (parse_redox_rust, which has no LOC).
+UNSAFE_FN_FALSE_POSITIVES = {
+    "rust/src/libcore": 3,
+    "rust/src/liballoc": 3,
+}
+PRECOMPUTED_COUNTS = {
+    "redox": "redox_rust/.info",
+}
+
+# Same patterns as the egrep/grep calls in run_all.sh
+UNSAFE_FN_RE = re.compile(r"unsafe (\w )*fn .+\(")
+UNSAFE_TRAIT_RE = re.compile(r"unsafe trait .*{")
+
+class UnsafeCount:
+    def __init__(self):
+        self.unsafe_region_num = 0
+        self.unsafe_fn_num = 0
+        self.unsafe_trait_num = 0
+        self.total_LOC = 0
+
+    def add_lines(self, lines):
+        self.unsafe_region_num += len(extract_macro(lines))
+        self.unsafe_fn_num += len([l for l in lines if UNSAFE_FN_RE.search(l)
+                                   and not l.rstrip("\r\n").endswith(";")])
+        self.unsafe_trait_num += len([l for l in lines if UNSAFE_TRAIT_RE.search(l)])
+        self.total_LOC += len([l for l in lines if l.strip() != ""])
+
+    def unsafe_num(self):
+        return self.unsafe_region_num + self.unsafe_fn_num + self.unsafe_trait_num
+
+    def add(self, other):
+        self.unsafe_region_num += other.unsafe_region_num
+        self.unsafe_fn_num += other.unsafe_fn_num
+        self.unsafe_trait_num += other.unsafe_trait_num
+        self.total_LOC += other.total_LOC
+
+def remove_comment(path):
+    output = subprocess.run([COMMENT_REMOVER, path], stdout=subprocess.PIPE)
+    return output.stdout.decode("utf-8", errors="replace").splitlines(keepends=True)
+
+def rs_files(input_dir, skip_dir=None):
+    """`.rs` files below `input_dir`, not descending into the sub-directories
+    for which `skip_dir` returns True."""
+    for root, dirs, files in os.walk(input_dir):
+        if skip_dir is not None:
+            dirs[:] = [d for d in dirs if not skip_dir(os.path.join(root, d))]
+        for name in sorted(files):
+            if name.endswith(".rs"):
+                yield os.path.join(root, name)
+
+def project_dirs(input_dir, subdirs):
+    """Directories of a project under COUNT_DIR, skipping missing ones."""
+    dirs = []
+    for subdir in subdirs:
+        project_dir = os.path.join(input_dir, subdir)
+        if os.path.isdir(project_dir):
+            dirs.append(project_dir)
+        else:
+            print("skip missing project:", project_dir, file=sys.stderr)
+    return dirs
+
+def count_project(input_dir, project, subdirs):
+    """Count a project the way run_all.sh does, or return None if none of
+    its directories exist."""
+    dirs = project_dirs(input_dir, subdirs)
+    if not dirs:
+        return None
+    count = UnsafeCount()
+    for subdir in subdirs:
+        project_dir = os.path.join(input_dir, subdir)
+        if project_dir in dirs:
+            count.add(count_dir(project_dir))
+            count.unsafe_fn_num -= UNSAFE_FN_FALSE_POSITIVES.get(subdir, 0)
+    if project in PRECOMPUTED_COUNTS:
+        info_path = os.path.join(input_dir, PRECOMPUTED_COUNTS[project])
+        if os.path.exists(info_path):
+            with open(info_path) as infile:
+                fn_num, region_num, trait_num = [int(v) for v in infile.read().split()[:3]]
+            count.unsafe_fn_num += fn_num
+            count.unsafe_region_num += region_num
+            count.unsafe_trait_num += trait_num
+        else:
+            print("skip missing precomputed counts:", info_path, file=sys.stderr)
+    return count
+
+def count_dir(input_dir, skip_dir=None):
+    count = UnsafeCount()
+    for path in rs_files(input_dir, skip_dir):
+        count.add_lines(remove_comment(path))
+    return count
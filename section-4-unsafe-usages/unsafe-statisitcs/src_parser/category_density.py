@@ -0,0 +1,127 @@
+#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+
+# Compare unsafe density and bug density per KLOC across project categories.
+#
+# Usage: ./category_density.py COUNT_DIR
+#
+# Unsafe usages and LOC are counted over the benchmark snapshots in COUNT_DIR
+# (same layout as run_all.sh). Bug numbers are the studied memory, blocking,
+# and non-blocking bugs kept in this repository, one entry per bug. Bugs from
+# the CVE/RUSTSEC databases are not attributed to any benchmark project.
+#
+# Densities are events per KLOC with an exact (Garwood) 95% confidence
+# interval, treating the number of events in a category as a Poisson count.
+
+import math
+import os
+import sys
+
+from benchmark import PROJECTS, SRC_PARSER_DIR, count_project
+
+REPO_DIR = os.path.join(SRC_PARSER_DIR, "..", "..", "..")
+
+BUG_DIRS = [
+    ("memory", "section-5-memory-safety-issues/memory-bugs"),
+    ("blocking", "section-6-thread-safety-issues/section-6-1-blocking-bugs/blocking-bugs"),
+    ("non-blocking", "section-6-thread-safety-issues/section-6-2-non-blocking-bugs/non-blocking-bugs"),
+]
+
+# Project name in PROJECTS -> sub-directory name in BUG_DIRS
+BUG_SUBDIRS = {
+    "rand": "rand",
+    "crossbeam": "crossbeam",
+    "threadpool": "rust-threadpool",
+    "rayon": "rayon",
+    "lazy-static.rs": "lazy-static.rs",
+    "servo": "servo",
+    "tikv": "tikv",
+    "ethereum": "parity-ethereum",
+    "redox": "redox",
+    "tock": "tock",
+}
+
+CATEGORIES = [
+    ("OS", ["redox", "tock"]),
+    ("browser", ["servo"]),
+    ("blockchain", ["ethereum"]),
+    ("database", ["tikv"]),
+    ("library", ["rand", "crossbeam", "threadpool", "rayon", "lazy-static.rs"]),
+]
+
+ALPHA = 0.05
+
+def count_bugs(project):
+    bugs = {}
+    for kind, bug_dir in BUG_DIRS:
+        path = os.path.join(REPO_DIR, bug_dir, BUG_SUBDIRS[project])
+        bugs[kind] = len(os.listdir(path)) if os.path.isdir(path) else 0
+    return bugs
+
+def poisson_cdf(k, lam):
+    """P(X <= k) for X ~ Poisson(lam)."""
+    if lam == 0:
+        return 1.0
+    log_lam = math.log(lam)
+    return min(math.fsum(math.exp(i * log_lam - lam - math.lgamma(i + 1)) for i in range(k + 1)), 1.0)
+
+def solve_decreasing(f, target):
+    """lam with f(lam) == target, for f decreasing from 1 to 0 on [0, inf)."""
+    low, high = 0.0, 1.0
+    while f(high) > target:
+        high *= 2
+    for _ in range(100):
+        mid = (low + high) / 2
+        if f(mid) > target:
+            low = mid
+        else:
+            high = mid
+    return (low + high) / 2
+
+def poisson_interval(num):
+    """Exact two-sided interval for the mean of a Poisson count, i.e. the
+    chi-square quantiles chi2(ALPHA/2, 2num)/2 and chi2(1-ALPHA/2, 2num+2)/2."""
+    if num == 0:
+        low = 0.0
+    else:
+        low = solve_decreasing(lambda lam: poisson_cdf(num - 1, lam), 1 - ALPHA / 2)
+    high = solve_decreasing(lambda lam: poisson_cdf(num, lam), ALPHA / 2)
+    return (low, high)
+
+def density(num, KLOC):
+    if KLOC == 0:
+        return (0.0, 0.0, 0.0)
+    low, high = poisson_interval(num)
+    return (num / KLOC, low / KLOC, high / KLOC)
+
+def fmt(value):
+    return "%.3f" % value
+
+def main():
+    input_dir = sys.argv[1]
+    counts = {}
+    for project, subdirs in PROJECTS:
+        if project not in BUG_SUBDIRS:
+            continue
+        count = count_project(input_dir, project, subdirs)
+        if count is None:
+            continue
+        counts[project] = (count, count_bugs(project))
+
+    print("category,projects,KLOC,unsafe num,unsafe/KLOC,unsafe/KLOC low,unsafe/KLOC high,"
+          "bug num,memory,blocking,non-blocking,bugs/KLOC,bugs/KLOC low,bugs/KLOC high")
+    for category, projects in CATEGORIES:
+        projects = [p for p in projects if p in counts]
+        if not projects:
+            continue
+        KLOC = sum(counts[p][0].total_LOC for p in projects) / 1000.0
+        unsafe_num = sum(counts[p][0].unsafe_num() for p in projects)
+        bugs = {kind: sum(counts[p][1][kind] for p in projects) for kind, _ in BUG_DIRS}
+        bug_num = sum(bugs.values())
+        print(",".join([category, " ".join(projects), fmt(KLOC), str(unsafe_num)]
+                       + [fmt(v) for v in density(unsafe_num, KLOC)]
+                       + [str(bug_num)] + [str(bugs[kind]) for kind, _ in BUG_DIRS]
+                       + [fmt(v) for v in density(bug_num, KLOC)]))
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,77 @@
+#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+
+import re
+import sys
+
+class FeatureGateInfo:
+    def __init__(self):
+        self.line_no = 0
+        self.name = ""
+    def __str__(self):
+        return str(self.line_no) + "," + self.name
+    def __repr__(self):
+        return str(self.line_no) + "," + self.name
+
+# `feature(...)` inside a crate-level attribute, but not the `feature = "..."`
+# predicate of `cfg_attr`
+FEATURE_RE = re.compile(r"(?<![\w=])feature\(([^()]*)\)")
+
+def extract_feature_gates(lines):
+    # Crate-level attributes may span lines, e.g.
+    #   #![feature(const_fn,
+    #              core_intrinsics)]
+    # and the gates may be enabled conditionally, e.g.
+    #   #![cfg_attr(feature = "nightly", feature(core_intrinsics))]
+    pattern = "#!["
+    pattern_len = len(pattern)
+    is_in_attr = False
+    feature_gate_infos = []
+    attr_start_line_no = 0
+    attr_body = ""
+    for idx, line in enumerate(lines):
+        if not is_in_attr:
+            pos = line.replace(" ", "").find(pattern)
+            if pos == -1:
+                continue
+            is_in_attr = True
+            attr_start_line_no = idx + 1
+            attr_body = line.replace(" ", "")[pos+pattern_len:]
+        else:
+            attr_body += line.replace(" ", "")
+        depth = 1
+        end = -1
+        for pos, ch in enumerate(attr_body):
+            if ch == "[":
+                depth += 1
+            elif ch == "]":
+                depth -= 1
+                if depth == 0:
+                    end = pos
+                    break
+        if end == -1:
+            continue
+        is_in_attr = False
+        attr = attr_body[:end]
+        if not (attr.startswith("feature(") or attr.startswith("cfg_attr(")):
+            continue
+        for m in FEATURE_RE.finditer(attr):
+            for name in m.group(1).split(","):
+                name = name.strip()
+                if name == "":
+                    continue
+                info = FeatureGateInfo()
+                info.line_no = attr_start_line_no
+                info.name = name
+                feature_gate_infos.append(info)
+    return feature_gate_infos
+
+def main():
+    with open(sys.argv[1]) as infile:
+        lines = infile.readlines()
+        feature_gate_infos = extract_feature_gates(lines)
+        for feature_gate_info in feature_gate_infos:
+            print(feature_gate_info)
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,25 @@
+#![feature(asm, core_intrinsics)]
+#![feature(ptr_internals)]
+#![feature(const_fn,
+           used,
+           naked_functions)]
+#![cfg_attr(feature = "nightly", feature(core_intrinsics))]
+#![cfg_attr(all(test, feature = "nightly"),
+            feature(test, allocator_api))]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![no_std]
+
+use core::intrinsics::{likely, unlikely};
+use core::ptr::Unique;
+
+pub struct Owned<T: ?Sized> {
+    data: Unique<T>,
+}
+
+#[inline(always)]
+pub fn check(x: usize) -> bool {
+    if unsafe { likely(x > 0) } {
+        return true;
+    }
+    unsafe { unlikely(x == 0) }
+}
@@ -0,0 +1,69 @@
+#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+
+# Collect every `#![feature(...)]` gate (also inside `#![cfg_attr(...)]`)
+# across the benchmark snapshots and correlate it with the unsafe usages of
+# the crates that enable it.
+#
+# Usage: ./feature_matrix.py COUNT_DIR
+#
+# A crate is approximated by the directory of the file that declares the
+# feature gates (normally `src/` holding `lib.rs` or `main.rs`). Its unsafe
+# regions, functions and traits are counted over the `.rs` files below it,
+# stopping at nested crates (directories with a `Cargo.toml` or their own
+# feature gates, e.g. `libcore/tests`), which are counted on their own.
+
+import os
+import sys
+
+from benchmark import PROJECTS, count_dir, project_dirs, remove_comment, rs_files
+from feature_gate_extractor import extract_feature_gates
+
+class CrateInfo:
+    def __init__(self, path):
+        self.path = path
+        self.features = []
+        self.count = None
+
+def collect_crates(project_dir):
+    crates = {}
+    for path in rs_files(project_dir):
+        lines = remove_comment(path)
+        features = extract_feature_gates(lines)
+        if not features:
+            continue
+        crate_dir = os.path.dirname(path)
+        crate = crates.setdefault(crate_dir, CrateInfo(crate_dir))
+        for info in features:
+            if info.name not in crate.features:
+                crate.features.append(info.name)
+    def is_nested_crate(path):
+        return path in crates or os.path.exists(os.path.join(path, "Cargo.toml"))
+    for crate in crates.values():
+        crate.count = count_dir(crate.path, is_nested_crate)
+    return list(crates.values())
+
+def main():
+    input_dir = sys.argv[1]
+    # feature -> project -> [CrateInfo]
+    matrix = {}
+    for project, subdirs in PROJECTS:
+        for project_dir in project_dirs(input_dir, subdirs):
+            for crate in collect_crates(project_dir):
+                for feature in crate.features:
+                    matrix.setdefault(feature, {}).setdefault(project, []).append(crate)
+
+    project_names = [project for project, _ in PROJECTS]
+    print("feature," + ",".join(project_names) + ",crates,unsafe region num,unsafe fn num,unsafe trait num")
+    for feature in sorted(matrix, key=lambda f: (-sum(len(c) for c in matrix[f].values()), f)):
+        row = matrix[feature]
+        crates = [crate for project in project_names for crate in row.get(project, [])]
+        print(",".join([feature]
+                       + [str(len(row.get(project, []))) for project in project_names]
+                       + [str(len(crates)),
+                          str(sum(c.count.unsafe_region_num for c in crates)),
+                          str(sum(c.count.unsafe_fn_num for c in crates)),
+                          str(sum(c.count.unsafe_trait_num for c in crates))]))
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,152 @@
+#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+
+# Find public safe functions and trait-impl methods whose body is mostly a
+# single unsafe block ("thin safe wrappers") and check whether their doc
+# comments state the preconditions the unsafe code relies on.
+#
+# Usage: ./safe_wrapper_extractor.py FILE
+#
+# Output: fn start line, fn end line, name, body LOC, unsafe LOC,
+#         documented (0/1), precondition documented (0/1)
+
+import os
+import re
+import subprocess
+import sys
+
+sys.path.append(os.path.join(os.path.dirname(os.path.abspath(__file__)), "..", "unsafe_block_extractor"))
+
+from unsafe_block_extractor import extract_macro
+
+# Only bare `pub`: `pub(crate)`, `pub(super)` and `pub(in path)` functions
+# are not callable by users of the crate.
+PUB_FN_RE = re.compile(r"\bpub\s+(const\s+)?(?P<unsafe>unsafe\s+)?(extern\s+\"[^\"]*\"\s+)?fn\s+(?P<name>\w+)")
+# Methods of `impl Trait for Type` blocks have no `pub`, but are as public
+# as the trait and the type.
+TRAIT_FN_RE = re.compile(r"^\s*(default\s+)?(const\s+)?(?P<unsafe>unsafe\s+)?(extern\s+\"[^\"]*\"\s+)?fn\s+(?P<name>\w+)")
+TRAIT_IMPL_RE = re.compile(r"^\s*(unsafe\s+)?impl\b.*\sfor\s")
+PRECONDITION_RE = re.compile(r"# Safety|\bprecondition|\bundefined behaviou?r"
+                             r"|\b(caller|callers|you)\s+(must|should|has to|have to)\b"
+                             r"|\bmust\s+(be|not|have|point|outlive|remain)\b"
+                             r"|\b(assumes?|requires?)\s+that\b", re.IGNORECASE)
+
+# Minimum share of the body lines inside the unsafe block
+WRAPPER_RATIO = 0.8
+
+class SafeWrapperInfo:
+    def __init__(self):
+        self.start_line_no = 0
+        self.end_line_no = 0
+        self.name = ""
+        self.body_LOC = 0
+        self.unsafe_LOC = 0
+        self.documented = False
+        self.precondition_documented = False
+    def __str__(self):
+        return ",".join([str(self.start_line_no), str(self.end_line_no), self.name,
+                         str(self.body_LOC), str(self.unsafe_LOC),
+                         str(int(self.documented)), str(int(self.precondition_documented))])
+    def __repr__(self):
+        return self.__str__()
+
+def find_body(lines, start):
+    """Return (open line index, close line index) of the fn body starting at
+    line `start`, or None for a declaration without body. A `;` inside the
+    parentheses or brackets of the signature (e.g. `[u8; 32]`) does not end
+    the declaration."""
+    left = 0
+    open_idx = -1
+    nesting = 0
+    for idx in range(start, len(lines)):
+        for ch in lines[idx]:
+            if open_idx == -1:
+                if ch in "([":
+                    nesting += 1
+                elif ch in ")]":
+                    nesting -= 1
+                elif ch == ';' and nesting == 0:
+                    return None
+            if ch == '{':
+                if open_idx == -1:
+                    open_idx = idx
+                left += 1
+            elif ch == '}':
+                left -= 1
+                if left == 0 and open_idx != -1:
+                    return (open_idx, idx)
+    return None
+
+def doc_comment(orig_lines, fn_idx):
+    docs = []
+    idx = fn_idx - 1
+    while idx >= 0:
+        line = orig_lines[idx].strip()
+        if line.startswith("///"):
+            docs.append(line[3:])
+        elif not line.startswith("#[") and line != "":
+            break
+        idx -= 1
+    return "\n".join(reversed(docs))
+
+def trait_impl_lines(lines):
+    """Indices of the lines inside `impl Trait for Type { ... }` blocks."""
+    inside = set()
+    for idx, line in enumerate(lines):
+        if TRAIT_IMPL_RE.search(line) is None:
+            continue
+        body = find_body(lines, idx)
+        if body is not None:
+            inside.update(range(body[0] + 1, body[1]))
+    return inside
+
+def extract_safe_wrappers(lines, orig_lines):
+    """`lines` is the file with comments removed (same line numbers as
+    `orig_lines`, which still has the doc comments)."""
+    safe_wrapper_infos = []
+    in_trait_impl = trait_impl_lines(lines)
+    for idx, line in enumerate(lines):
+        m = PUB_FN_RE.search(line)
+        if m is None and idx in in_trait_impl:
+            m = TRAIT_FN_RE.search(line)
+        if m is None or m.group("unsafe") is not None:
+            continue
+        body = find_body(lines, idx)
+        if body is None:
+            continue
+        open_idx, close_idx = body
+        if open_idx == close_idx:
+            inner = [lines[open_idx][lines[open_idx].find("{") + 1:]]
+        else:
+            inner = lines[open_idx + 1:close_idx]
+        blocks = extract_macro(inner)
+        if len(blocks) != 1:
+            continue
+        block = blocks[0]
+        body_LOC = len([l for l in inner if l.strip() != ""])
+        unsafe_LOC = len([l for l in inner[block.start_line_no - 1:block.end_line_no] if l.strip() != ""])
+        if body_LOC == 0 or unsafe_LOC / body_LOC < WRAPPER_RATIO:
+            continue
+        info = SafeWrapperInfo()
+        info.start_line_no = idx + 1
+        info.end_line_no = close_idx + 1
+        info.name = m.group("name")
+        info.body_LOC = body_LOC
+        info.unsafe_LOC = unsafe_LOC
+        docs = doc_comment(orig_lines, idx)
+        info.documented = docs.strip() != ""
+        info.precondition_documented = PRECONDITION_RE.search(docs) is not None
+        safe_wrapper_infos.append(info)
+    return safe_wrapper_infos
+
+def main():
+    comment_remover = os.path.join(os.path.dirname(os.path.abspath(__file__)), "..", "comment_remover", "comment_remover")
+    output = subprocess.run([comment_remover, sys.argv[1]], stdout=subprocess.PIPE)
+    lines = output.stdout.decode("utf-8", errors="replace").splitlines(keepends=True)
+    with open(sys.argv[1], errors="replace") as infile:
+        orig_lines = infile.readlines()
+        for safe_wrapper_info in extract_safe_wrappers(lines, orig_lines):
+            print(safe_wrapper_info)
+
+if __name__ == "__main__":
+    main()
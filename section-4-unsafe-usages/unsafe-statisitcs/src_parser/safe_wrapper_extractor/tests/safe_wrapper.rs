@@ -0,0 +1,88 @@
+use core::ptr;
+
+pub struct Buffer {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Buffer {
+    /// Returns the byte at `idx`.
+    ///
+    /// The caller must make sure `idx < self.len()`.
+    pub fn get(&self, idx: usize) -> u8 {
+        unsafe {
+            *self.ptr.add(idx)
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] { unsafe { core::slice::from_raw_parts(self.ptr, self.len) } }
+
+    /// Overwrites the first byte.
+    #[inline]
+    pub fn set_first(&mut self, val: u8) {
+        unsafe {
+            ptr::write(self.ptr, val);
+        }
+    }
+
+    /// Bounds checked, so not a thin wrapper.
+    pub fn get_checked(&self, idx: usize) -> Option<u8> {
+        if idx >= self.len {
+            return None;
+        }
+        let val = unsafe { *self.ptr.add(idx) };
+        Some(val)
+    }
+
+    /// Not public outside the crate, so not counted.
+    pub(crate) fn last(&self) -> u8 {
+        unsafe { *self.ptr.add(self.len - 1) }
+    }
+
+    pub unsafe fn get_unchecked(&self, idx: usize) -> u8 {
+        *self.ptr.add(idx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pub trait Reader {
+    fn read(&mut self) -> u8;
+    fn peek(&self) -> u8;
+}
+
+pub struct Key {
+    bytes: [u8; 32],
+}
+
+impl Key {
+    pub fn bytes(&self) -> &[u8; 32] {
+        unsafe { &*(self.bytes.as_ptr() as *const [u8; 32]) }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the key is not valid.
+    pub fn first(&self) -> u8 {
+        unsafe { *self.bytes.get_unchecked(0) }
+    }
+}
+
+impl core::ops::Deref for Buffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl core::ops::Index<usize> for Buffer {
+    type Output = u8;
+
+    /// `idx` must be in bounds.
+    fn index(&self, idx: usize) -> &u8 {
+        unsafe { &*self.ptr.add(idx) }
+    }
+}
@@ -0,0 +1,45 @@
+#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+
+# List the thin safe wrappers of every benchmark app, i.e., public safe
+# functions whose body is predominantly a single unsafe block, together
+# with whether their doc comments state preconditions.
+#
+# Usage: ./safe_wrappers.py COUNT_DIR OUT_DIR
+#
+# One CSV per project is written to OUT_DIR, and a per-project summary is
+# printed to stdout.
+
+import os
+import sys
+
+from benchmark import PROJECTS, project_dirs, remove_comment, rs_files
+from safe_wrapper_extractor import extract_safe_wrappers
+
+def main():
+    input_dir = sys.argv[1]
+    out_dir = sys.argv[2]
+    os.makedirs(out_dir, exist_ok=True)
+    print("project,safe wrapper num,documented,precondition documented")
+    for project, subdirs in PROJECTS:
+        dirs = project_dirs(input_dir, subdirs)
+        if not dirs:
+            continue
+        wrapper_num = 0
+        documented = 0
+        precondition_documented = 0
+        with open(os.path.join(out_dir, project + ".csv"), "w") as outfile:
+            outfile.write("file,start line,end line,fn,body LOC,unsafe LOC,documented,precondition documented\n")
+            for path in [path for project_dir in dirs for path in rs_files(project_dir)]:
+                lines = remove_comment(path)
+                with open(path, errors="replace") as infile:
+                    orig_lines = infile.readlines()
+                for info in extract_safe_wrappers(lines, orig_lines):
+                    outfile.write(os.path.relpath(path, input_dir) + "," + str(info) + "\n")
+                    wrapper_num += 1
+                    documented += int(info.documented)
+                    precondition_documented += int(info.precondition_documented)
+        print(",".join([project, str(wrapper_num), str(documented), str(precondition_documented)]))
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,96 @@
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+/// The data for testing.
+#[derive(Debug, Default)]
+struct Printer(Vec<i32>);
+
+impl Drop for Printer {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+        println!("Dropping vec: addr: {:?}, len: {}", self.0.as_ptr(), self.0.len());
+    }
+}
+
+/// A container that hands out the value stored in it, in the style of the
+/// slot/queue code in the studied libraries.
+struct Slot<T> {
+    value: T,
+}
+
+impl<T> Slot<T> {
+    /// Buggy code: `ptr::read` makes a bitwise copy, so the value now has two
+    /// owners. The caller drops the copy and the slot drops the original.
+    fn take_buggy(&mut self) -> T {
+        unsafe { ptr::read(&self.value) }
+    }
+}
+
+struct ManualSlot<T> {
+    value: ManuallyDrop<T>,
+}
+
+impl<T> ManualSlot<T> {
+    /// Patched code: the slot never drops what it stores, so moving the value
+    /// out leaves exactly one owner.
+    fn take(mut self) -> T {
+        unsafe { ManuallyDrop::take(&mut self.value) }
+    }
+}
+
+impl<T: Default> Slot<T> {
+    /// Patched code: overwrite the slot with `ptr::write` after reading, so
+    /// the stale bits are never dropped.
+    fn take_patch(&mut self) -> T {
+        unsafe {
+            let value = ptr::read(&self.value);
+            ptr::write(&mut self.value, T::default());
+            value
+        }
+    }
+}
+
+fn bug() {
+    {
+        let mut slot = Slot { value: Printer(vec![1, 2, 3]) };
+        let taken = slot.take_buggy();
+        println!("taken: {:?}", taken);
+        // `taken` and `slot.value` are both dropped here: double free.
+    }
+    println!("drops: {} (expected 1)", DROPS.load(Ordering::SeqCst));
+}
+
+#[allow(dead_code)]
+fn patch() {
+    {
+        let mut slot = Slot { value: Printer(vec![1, 2, 3]) };
+        let taken = slot.take_patch();
+        println!("taken: {:?}", taken);
+    }
+    {
+        let slot = ManualSlot { value: ManuallyDrop::new(Printer(vec![4, 5, 6])) };
+        let taken = slot.take();
+        println!("taken: {:?}", taken);
+    }
+    // Two allocated vectors plus the empty one left behind by `take_patch`.
+    println!("drops: {} (expected 3)", DROPS.load(Ordering::SeqCst));
+}
+
+/**
+ * How to reproduce this bug:
+ *     - cargo run
+ *       The vector buffer is freed twice; glibc usually aborts with
+ *       "free(): double free detected", otherwise "drops: 2" is printed.
+ *     - cargo +nightly miri run
+ *       Miri reports the second deallocation as undefined behavior.
+ *     - RUSTFLAGS="-Zsanitizer=address" cargo +nightly run --target x86_64-unknown-linux-gnu
+ *       ASan reports "attempting double-free".
+ *     - Comment out bug() and uncomment patch() to run the patched versions.
+ */
+fn main() {
+    bug();
+    // patch();
+}
@@ -0,0 +1,89 @@
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// The data for testing.
+#[derive(Debug, Default)]
+struct Printer(Vec<i32>);
+
+/// A job queue shared by worker threads, in the style of the pool code in
+/// the studied libraries.
+struct Queue {
+    jobs: Mutex<Printer>,
+}
+
+impl Queue {
+    fn new() -> Self {
+        Queue { jobs: Mutex::new(Printer(vec![1, 2, 3])) }
+    }
+
+    /// Buggy code: the guard is forgotten so that the "slow path" can keep
+    /// working on the jobs without the unlock, but nothing ever unlocks the
+    /// mutex afterwards. `mem::forget` is safe, so the compiler accepts it.
+    fn pop_buggy(&self) -> Option<i32> {
+        let mut guard = self.jobs.lock().unwrap();
+        let job = guard.0.pop();
+        if guard.0.is_empty() {
+            return job;
+        }
+        mem::forget(guard);
+        job
+    }
+
+    /// Patched code: the lock only lives inside the closure, so it is
+    /// released on every path out of it.
+    fn with_jobs<R>(&self, f: impl FnOnce(&mut Printer) -> R) -> R {
+        let mut guard = self.jobs.lock().unwrap();
+        f(&mut guard)
+    }
+
+    fn pop_patch(&self) -> Option<i32> {
+        self.with_jobs(|jobs| jobs.0.pop())
+    }
+}
+
+fn bug() {
+    let queue = Arc::new(Queue::new());
+    println!("popped: {:?}", queue.pop_buggy());
+    let worker = {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            println!("worker: waiting for the lock");
+            // The mutex is still held by the forgotten guard: this never returns.
+            println!("worker popped: {:?}", queue.pop_buggy());
+        })
+    };
+    worker.join().unwrap();
+    println!("done");
+}
+
+#[allow(dead_code)]
+fn patch() {
+    let queue = Arc::new(Queue::new());
+    println!("popped: {:?}", queue.pop_patch());
+    let worker = {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            println!("worker: waiting for the lock");
+            println!("worker popped: {:?}", queue.pop_patch());
+        })
+    };
+    worker.join().unwrap();
+    queue.with_jobs(|jobs| println!("left: {:?}", jobs));
+    println!("done");
+}
+
+/**
+ * How to reproduce this bug:
+ *     - cargo run
+ *       "worker: waiting for the lock" is printed and the program hangs,
+ *       since the mutex is never unlocked. No sanitizer reports anything:
+ *       leaking a guard is not undefined behavior.
+ *     - ./run_all.sh patterns/forget-lock-guard
+ *       The run is killed after the timeout and counted as a hang.
+ *     - Comment out bug() and uncomment patch() to run the patched version.
+ */
+fn main() {
+    bug();
+    // patch();
+}
@@ -0,0 +1,89 @@
+use std::slice;
+
+/// A TBF-style header in front of an app image:
+///     version: u16, header_size: u16, total_size: u32
+const HEADER_LEN: usize = 8;
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct Header {
+    version: u16,
+    header_size: u16,
+    total_size: u32,
+}
+
+fn parse_header(buf: &[u8]) -> Option<Header> {
+    if buf.len() < HEADER_LEN {
+        return None;
+    }
+    Some(Header {
+        version: u16::from_le_bytes([buf[0], buf[1]]),
+        header_size: u16::from_le_bytes([buf[2], buf[3]]),
+        total_size: u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]),
+    })
+}
+
+/// Buggy code: the lengths come from the image itself and are trusted, so a
+/// `total_size` larger than the buffer makes the payload slice run past it.
+fn payload_bug(buf: &[u8]) -> Option<&[u8]> {
+    let header = parse_header(buf)?;
+    println!("header: {:?}, buffer len: {}", header, buf.len());
+    let start = header.header_size as usize;
+    let len = header.total_size as usize - start;
+    unsafe { Some(slice::from_raw_parts(buf.as_ptr().add(start), len)) }
+}
+
+/// Patched code: both lengths are validated against the backing buffer with
+/// checked arithmetic before the slice is created.
+fn payload_patch(buf: &[u8]) -> Option<&[u8]> {
+    let header = parse_header(buf)?;
+    println!("header: {:?}, buffer len: {}", header, buf.len());
+    let start = header.header_size as usize;
+    let end = header.total_size as usize;
+    if start < HEADER_LEN || end > buf.len() {
+        return None;
+    }
+    let len = end.checked_sub(start)?;
+    unsafe { Some(slice::from_raw_parts(buf.as_ptr().add(start), len)) }
+}
+
+fn image() -> Vec<u8> {
+    // The header claims 64 bytes in total, but only 16 bytes are present.
+    let mut buf = vec![];
+    buf.extend_from_slice(&2u16.to_le_bytes());
+    buf.extend_from_slice(&(HEADER_LEN as u16).to_le_bytes());
+    buf.extend_from_slice(&64u32.to_le_bytes());
+    buf.extend_from_slice(&[0xaa; 8]);
+    buf
+}
+
+fn bug() {
+    let buf = image();
+    if let Some(payload) = payload_bug(&buf) {
+        let sum: u32 = payload.iter().map(|&b| b as u32).sum();
+        println!("payload len: {}, checksum: {}", payload.len(), sum);
+    }
+}
+
+#[allow(dead_code)]
+fn patch() {
+    let buf = image();
+    match payload_patch(&buf) {
+        Some(payload) => println!("payload len: {}", payload.len()),
+        None => println!("rejected malformed header"),
+    }
+}
+
+/**
+ * How to reproduce this bug:
+ *     - cargo run
+ *       The checksum covers 56 bytes although only 8 payload bytes exist;
+ *       the extra bytes are heap memory past the end of the buffer.
+ *     - RUSTFLAGS="-Zsanitizer=address" cargo +nightly run --target x86_64-unknown-linux-gnu
+ *       ASan reports "heap-buffer-overflow" on the read.
+ *     - Comment out bug() and uncomment patch() to run the patched version.
+ */
+fn main() {
+    bug();
+    // patch();
+}
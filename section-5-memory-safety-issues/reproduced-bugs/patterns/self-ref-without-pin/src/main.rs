@@ -0,0 +1,105 @@
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::ptr;
+
+/// A parser that keeps a cursor pointing into its own buffer.
+struct Parser {
+    buf: [u8; 16],
+    cursor: *const u8,
+}
+
+impl Parser {
+    fn new(input: &[u8]) -> Parser {
+        let mut buf = [0; 16];
+        buf[..input.len()].copy_from_slice(input);
+        Parser { buf, cursor: ptr::null() }
+    }
+
+    fn init(&mut self) {
+        self.cursor = self.buf.as_ptr();
+    }
+
+    /// The cursor is only valid while the parser stays where `init` saw it.
+    fn peek(&self) -> u8 {
+        unsafe { *self.cursor }
+    }
+}
+
+/// Patched code: `PhantomPinned` plus `Pin<Box<_>>` make moving the parser
+/// out of its allocation a compile error once the cursor is set.
+struct PinnedParser {
+    buf: [u8; 16],
+    cursor: *const u8,
+    _pin: PhantomPinned,
+}
+
+impl PinnedParser {
+    fn new(input: &[u8]) -> Pin<Box<PinnedParser>> {
+        let mut buf = [0; 16];
+        buf[..input.len()].copy_from_slice(input);
+        let mut parser = Box::pin(PinnedParser { buf, cursor: ptr::null(), _pin: PhantomPinned });
+        unsafe {
+            let p = parser.as_mut().get_unchecked_mut();
+            p.cursor = p.buf.as_ptr();
+        }
+        parser
+    }
+
+    fn peek(self: Pin<&Self>) -> u8 {
+        unsafe { *self.cursor }
+    }
+}
+
+/// Patched code: keep an index instead of a pointer, so moving is harmless.
+struct IndexParser {
+    buf: [u8; 16],
+    cursor: usize,
+}
+
+impl IndexParser {
+    fn peek(&self) -> u8 {
+        self.buf[self.cursor]
+    }
+}
+
+fn bug() {
+    let mut boxed = Box::new(Parser::new(b"hello"));
+    boxed.init();
+    println!("before move: {}", boxed.peek() as char);
+
+    // Moving the parser out of the box frees the box at the end of the
+    // block, but the cursor still points into the freed heap buffer.
+    let moved: Parser = {
+        let boxed = boxed;
+        *boxed
+    };
+    println!("cursor: {:?}, buf: {:?}", moved.cursor, moved.buf.as_ptr());
+    println!("after move: {}", moved.peek() as char);
+}
+
+#[allow(dead_code)]
+fn patch() {
+    let pinned = PinnedParser::new(b"hello");
+    // let moved: PinnedParser = *Pin::into_inner(pinned); // does not compile
+    println!("pinned: {}", pinned.as_ref().peek() as char);
+
+    let boxed = Box::new(IndexParser { buf: *b"hello, world!!!!", cursor: 0 });
+    let moved: IndexParser = *boxed;
+    println!("index: {}", moved.peek() as char);
+}
+
+/**
+ * How to reproduce this bug:
+ *     - cargo run
+ *       "after move" reads whatever is left in the freed allocation; the
+ *       printed cursor and buffer addresses differ.
+ *     - cargo +nightly miri run
+ *       Miri reports the read as a use of a dangling pointer.
+ *     - RUSTFLAGS="-Zsanitizer=address" cargo +nightly run --target x86_64-unknown-linux-gnu
+ *       ASan reports "heap-use-after-free".
+ *     - Comment out bug() and uncomment patch() to run the patched versions.
+ */
+fn main() {
+    bug();
+    // patch();
+}
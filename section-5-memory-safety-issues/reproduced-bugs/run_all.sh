@@ -0,0 +1,149 @@
+#!/usr/bin/env bash
+#
+# Run reproduced bug cases and record how often each one reproduces.
+#
+# Usage: ./run_all.sh [-n ITERATIONS] [-q THRESHOLD] [-t TIMEOUT] [-r] [-a] [CASE_DIR...]
+#
+#   -n  run each case N times (stress mode, default 1)
+#   -q  quarantine cases that reproduce in less than THRESHOLD percent
+#       of the runs (default 50)
+#   -t  kill a run after TIMEOUT seconds; a killed run counts as reproduced,
+#       since it is how blocking bugs and leaked locks show up, and is also
+#       counted in the "hung" column (default 10)
+#   -r  build and run in release mode
+#   -a  build with AddressSanitizer (needs nightly), so that silent memory
+#       corruption such as an over-read also makes the run fail
+#
+# Without CASE_DIR, every case under this directory is run, i.e. every crate
+# with a binary (`src/main.rs` or `[[bin]]`) that no other case uses as a path
+# dependency (library checkouts such as `cve/crossbeam` are not cases). A run
+# counts as reproduced when the program exits with a non-zero status, is
+# killed by a signal (e.g. a double free abort), or times out. A missing
+# binary, or exit status 126/127 (not executable/not found), is a runner
+# error and is reported as such instead.
+
+iterations=1
+threshold=50
+timeout_sec=10
+profile=debug
+build_flag=""
+target_dir=target
+
+while getopts "n:q:t:ra" opt; do
+    case ${opt} in
+        n) iterations=${OPTARG} ;;
+        q) threshold=${OPTARG} ;;
+        t) timeout_sec=${OPTARG} ;;
+        r) profile=release; build_flag="--release" ;;
+        a) asan=1 ;;
+        *) exit 1 ;;
+    esac
+done
+shift $((OPTIND - 1))
+
+function check_positive() {
+    if ! [[ "$2" =~ ^[0-9]+$ ]] || [ "$2" -lt 1 ]; then
+        echo "$1 must be a positive integer" >&2
+        exit 1
+    fi
+}
+
+check_positive ITERATIONS "${iterations}"
+check_positive THRESHOLD "${threshold}"
+check_positive TIMEOUT "${timeout_sec}"
+if [ "${threshold}" -gt 100 ]; then
+    echo "THRESHOLD must be at most 100" >&2
+    exit 1
+fi
+
+if [ -n "${asan}" ]; then
+    target=$(rustc +nightly -vV | grep '^host' | cut -d' ' -f2)
+    export RUSTFLAGS="-Zsanitizer=address"
+    build_flag="+nightly build ${build_flag} --target ${target}"
+    target_dir=target/${target}
+else
+    build_flag="build ${build_flag}"
+fi
+
+root_dir=$(cd "$(dirname "$0")" && pwd)
+report=${root_dir}/stress_report.txt
+quarantine=${root_dir}/quarantine.txt
+
+# Directories that some crate under root_dir uses as a path dependency
+function path_deps() {
+    find "${root_dir}" -name Cargo.toml -not -path "*/target/*" | while read -r manifest; do
+        grep -o 'path *= *"[^"]*"' "${manifest}" | cut -d'"' -f2 | while read -r dep; do
+            realpath -m "$(dirname "${manifest}")/${dep}"
+        done
+    done | sort -u
+}
+
+if [ $# -eq 0 ]; then
+    deps=$(path_deps)
+    cases=()
+    for case_dir in $(find "${root_dir}" -name Cargo.toml -not -path "*/target/*" -printf "%h\n" | sort); do
+        if echo "${deps}" | grep -qxF "${case_dir}"; then
+            continue
+        fi
+        if [ ! -f "${case_dir}/src/main.rs" ] && ! grep -q '^\[\[bin\]\]' "${case_dir}/Cargo.toml"; then
+            continue
+        fi
+        cases+=("${case_dir}")
+    done
+    set -- "${cases[@]}"
+fi
+
+rm -f "${report}" "${quarantine}"
+echo "case, runs, reproduced, hung, frequency(%), status" | tee "${report}"
+
+for case_dir in "$@"; do
+    case_dir=$(cd "${case_dir}" && pwd)
+    name=$(grep -m1 '^name' "${case_dir}/Cargo.toml" | cut -d'"' -f2)
+    case_name=${case_dir#${root_dir}/}
+
+    if ! (cd "${case_dir}" && cargo ${build_flag} -q 2>/dev/null); then
+        echo "${case_name}, 0, 0, 0, 0, build-failed" | tee -a "${report}"
+        continue
+    fi
+
+    binary=${case_dir}/${target_dir}/${profile}/${name}
+    if [ ! -x "${binary}" ]; then
+        echo "${case_name}, 0, 0, 0, 0, runner-error" | tee -a "${report}"
+        continue
+    fi
+
+    reproduced=0
+    hung=0
+    runner_error=""
+    for ((i = 0; i < iterations; i++)); do
+        { (cd "${case_dir}" && timeout -s KILL "${timeout_sec}" "${binary}") >/dev/null 2>&1; } 2>/dev/null
+        ret=$?
+        if [ ${ret} -eq 126 ] || [ ${ret} -eq 127 ]; then
+            runner_error=1
+            break
+        fi
+        if [ ${ret} -ne 0 ]; then
+            let reproduced+=1
+        fi
+        # timeout exits with 128 + 9 when it had to kill the run
+        if [ ${ret} -eq 137 ]; then
+            let hung+=1
+        fi
+    done
+
+    if [ -n "${runner_error}" ]; then
+        echo "${case_name}, ${i}, ${reproduced}, ${hung}, 0, runner-error" | tee -a "${report}"
+        continue
+    fi
+
+    let frequency=reproduced*100/iterations
+    if [ ${frequency} -eq 100 ]; then
+        status=deterministic
+    elif [ ${frequency} -ge ${threshold} ]; then
+        status=flaky
+    else
+        status=quarantined
+        echo "${case_name}" >> "${quarantine}"
+    fi
+    echo "${case_name}, ${iterations}, ${reproduced}, ${hung}, ${frequency}, ${status}" | tee -a "${report}"
+done